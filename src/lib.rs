@@ -3,7 +3,12 @@
 /// An error that occurred while parsing.
 #[derive(Debug)]
 pub enum ParseError<'a> {
-  Backtrace,
+  /// The combinator didn't match and the next alternative should be
+  /// tried. Carries the labels of everything that was `expect`ed
+  /// along the way, so that if every alternative in an `or`/`or3..or7`
+  /// backtraces, the labels can be combined into a single
+  /// "expected one of: ..." message.
+  Backtrace(Vec<&'static str>),
   /// Parsing should completely fail.
   Failure(ParseErrorFailure<'a>),
 }
@@ -29,6 +34,28 @@ impl<'a> ParseErrorFailure<'a> {
     ParseErrorFailure::new(input, "Unexpected character.")
   }
 
+  /// Opinionated helper used to fail when every alternative in an
+  /// `or`/`or3..or7` backtraced. If any alternative was wrapped in
+  /// `expect`, synthesizes a message describing the union of what
+  /// was expected; otherwise falls back to the generic trailing-input
+  /// message.
+  pub fn new_for_backtrace(input: &'a str, labels: &[&'static str]) -> Self {
+    if labels.is_empty() {
+      ParseErrorFailure::new_for_trailing_input(input)
+    } else {
+      let mut unique_labels = Vec::with_capacity(labels.len());
+      for label in labels {
+        if !unique_labels.contains(label) {
+          unique_labels.push(*label);
+        }
+      }
+      ParseErrorFailure::new(
+        input,
+        format!("expected one of: {}", unique_labels.join(", ")),
+      )
+    }
+  }
+
   /// Opinionated helper to turn this failure into a result.
   pub fn into_result<T>(&self) -> Result<T, ParseErrorFailureError> {
     Err(self.into_error())
@@ -43,6 +70,46 @@ impl<'a> ParseErrorFailure<'a> {
       self.input.chars().take(60).collect::<String>()
     ))
   }
+
+  /// Opinionated helper to turn this failure into a result, using the
+  /// original, full input to compute the exact line and column the
+  /// failure occurred at.
+  pub fn into_result_with_original<T>(
+    &self,
+    original: &'a str,
+  ) -> Result<T, ParseErrorFailureError> {
+    Err(self.into_error_with_original(original))
+  }
+
+  /// Opinionated helper to turn this failure into a
+  /// `ParseErrorFailureError` that shows the offending source line
+  /// along with a caret pointing at the exact column the failure
+  /// occurred at, computed from the original, full input.
+  pub fn into_error_with_original(
+    &self,
+    original: &'a str,
+  ) -> ParseErrorFailureError {
+    let offset = original.len() - self.input.len();
+    let mut line = 1;
+    let mut line_start = 0;
+    for (pos, c) in original[..offset].char_indices() {
+      if c == '\n' {
+        line += 1;
+        line_start = pos + 1;
+      }
+    }
+    let column = original[line_start..offset].chars().count() + 1;
+    let source_line = original[line_start..].lines().next().unwrap_or("");
+    ParseErrorFailureError(format!(
+      "{} ({}:{})\n  {}\n  {}^",
+      self.message,
+      line,
+      column,
+      // truncate the output to prevent wrapping in the console
+      source_line.chars().take(60).collect::<String>(),
+      " ".repeat(column - 1),
+    ))
+  }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -71,7 +138,7 @@ impl<'a> ParseError<'a> {
   }
 
   pub fn backtrace<O>() -> ParseResult<'a, O> {
-    Err(ParseError::Backtrace)
+    Err(ParseError::Backtrace(Vec::new()))
   }
 }
 
@@ -89,13 +156,150 @@ pub fn with_failure_handling<'a, T>(
         Ok(result)
       }
     }
-    Err(ParseError::Backtrace) => {
-      ParseErrorFailure::new_for_trailing_input(input).into_result()
+    Err(ParseError::Backtrace(labels)) => {
+      ParseErrorFailure::new_for_backtrace(input, &labels).into_result()
     }
     Err(ParseError::Failure(e)) => e.into_result(),
   }
 }
 
+/// Opinionated helper that converts a combinator into a
+/// Result<T, ParseErrorFailureError>, using the original, full input
+/// to report the exact line and column a failure occurred at instead
+/// of just the failing suffix.
+pub fn parse_with_failure_handling<'a, T>(
+  original: &'a str,
+  combinator: impl Fn(&'a str) -> ParseResult<T>,
+) -> Result<T, ParseErrorFailureError> {
+  match combinator(original) {
+    Ok((input, result)) => {
+      if !input.is_empty() {
+        ParseErrorFailure::new_for_trailing_input(input)
+          .into_result_with_original(original)
+      } else {
+        Ok(result)
+      }
+    }
+    Err(ParseError::Backtrace(labels)) => {
+      ParseErrorFailure::new_for_backtrace(original, &labels)
+        .into_result_with_original(original)
+    }
+    Err(ParseError::Failure(e)) => e.into_result_with_original(original),
+  }
+}
+
+/// Allows chaining combinators together (ex. `parser.map(...).or(...)`).
+pub trait Parser<'a, O> {
+  /// Runs the parser against the provided input.
+  fn parse(&self, input: &'a str) -> ParseResult<'a, O>;
+
+  /// See `map`.
+  fn map<R>(self, func: impl Fn(O) -> R + 'a) -> BoxedParser<'a, R>
+  where
+    Self: Sized + 'a,
+    O: 'a,
+    R: 'a,
+  {
+    let parser = self;
+    BoxedParser::new(map(move |input| parser.parse(input), func))
+  }
+
+  /// See `or`.
+  fn or(self, other: impl Parser<'a, O> + 'a) -> BoxedParser<'a, O>
+  where
+    Self: Sized + 'a,
+    O: 'a,
+  {
+    let parser = self;
+    BoxedParser::new(or(
+      move |input| parser.parse(input),
+      move |input| other.parse(input),
+    ))
+  }
+
+  /// Runs this parser, then uses its output to construct and run a
+  /// second parser on the remaining input.
+  fn and_then<R>(
+    self,
+    func: impl Fn(O) -> BoxedParser<'a, R> + 'a,
+  ) -> BoxedParser<'a, R>
+  where
+    Self: Sized + 'a,
+    O: 'a,
+    R: 'a,
+  {
+    let parser = self;
+    BoxedParser::new(move |input| {
+      let (input, value) = parser.parse(input)?;
+      func(value).parse(input)
+    })
+  }
+
+  /// See `terminated`. Discards the value parsed by `separator`.
+  fn terminated<S>(self, separator: impl Parser<'a, S> + 'a) -> BoxedParser<'a, O>
+  where
+    Self: Sized + 'a,
+    O: 'a,
+    S: 'a,
+  {
+    let parser = self;
+    BoxedParser::new(terminated(
+      move |input| parser.parse(input),
+      move |input| separator.parse(input),
+    ))
+  }
+
+  /// See `preceded`. Discards the value parsed by `first`.
+  fn preceded<F>(self, first: impl Parser<'a, F> + 'a) -> BoxedParser<'a, O>
+  where
+    Self: Sized + 'a,
+    O: 'a,
+    F: 'a,
+  {
+    let parser = self;
+    BoxedParser::new(preceded(
+      move |input| first.parse(input),
+      move |input| parser.parse(input),
+    ))
+  }
+
+  /// See `maybe`.
+  fn maybe(self) -> BoxedParser<'a, Option<O>>
+  where
+    Self: Sized + 'a,
+    O: 'a,
+  {
+    let parser = self;
+    BoxedParser::new(maybe(move |input| parser.parse(input)))
+  }
+}
+
+impl<'a, O, F> Parser<'a, O> for F
+where
+  F: Fn(&'a str) -> ParseResult<'a, O>,
+{
+  fn parse(&self, input: &'a str) -> ParseResult<'a, O> {
+    self(input)
+  }
+}
+
+/// A parser that has been boxed up behind a trait object, which keeps
+/// the return types of the `Parser` combinator methods manageable
+/// (ex. when using `and_then` or building up deeply chained parsers).
+pub struct BoxedParser<'a, O>(Box<dyn Fn(&'a str) -> ParseResult<'a, O> + 'a>);
+
+impl<'a, O> BoxedParser<'a, O> {
+  pub fn new(combinator: impl Fn(&'a str) -> ParseResult<'a, O> + 'a) -> Self {
+    BoxedParser(Box::new(combinator))
+  }
+}
+
+impl<'a, O> Parser<'a, O> for BoxedParser<'a, O> {
+  fn parse(&self, input: &'a str) -> ParseResult<'a, O> {
+    (self.0)(input)
+  }
+}
+
 /// Recognizes a character.
 pub fn ch<'a>(c: char) -> impl Fn(&'a str) -> ParseResult<'a, char> {
   if_true(next_char, move |found_char| *found_char == c)
@@ -133,7 +337,7 @@ pub fn tag<'a>(
     if input.starts_with(&value) {
       Ok((&input[value.len()..], &input[..value.len()]))
     } else {
-      Err(ParseError::Backtrace)
+      ParseError::backtrace()
     }
   }
 }
@@ -178,7 +382,7 @@ pub fn maybe<'a, O>(
 ) -> impl Fn(&'a str) -> ParseResult<'a, Option<O>> {
   move |input| match combinator(input) {
     Ok((input, value)) => Ok((input, Some(value))),
-    Err(ParseError::Backtrace) => Ok((input, None)),
+    Err(ParseError::Backtrace(_)) => Ok((input, None)),
     Err(err) => Err(err),
   }
 }
@@ -202,14 +406,42 @@ pub fn map_res<'a, O, R>(
   move |input| func(combinator(input))
 }
 
-/// Checks for either to match.
+/// Wraps a combinator with a human-readable label describing what it
+/// matches. Behaves exactly like the inner combinator, except that a
+/// `Backtrace` has `label` recorded into it, so that if every branch
+/// of a surrounding `or`/`or3..or7` backtraces, the top-level error
+/// can be synthesized as `expected one of: <label_a>, <label_b>, ...`
+/// instead of the generic trailing-input message.
+pub fn expect<'a, O>(
+  label: &'static str,
+  combinator: impl Fn(&'a str) -> ParseResult<'a, O>,
+) -> impl Fn(&'a str) -> ParseResult<'a, O> {
+  move |input| match combinator(input) {
+    Err(ParseError::Backtrace(mut labels)) => {
+      labels.push(label);
+      Err(ParseError::Backtrace(labels))
+    }
+    result => result,
+  }
+}
+
+/// Checks for either to match. If both backtrace, the labels recorded
+/// by any `expect`-wrapped alternative are combined so the top-level
+/// error can describe the union of what was expected.
 pub fn or<'a, O>(
   a: impl Fn(&'a str) -> ParseResult<'a, O>,
   b: impl Fn(&'a str) -> ParseResult<'a, O>,
 ) -> impl Fn(&'a str) -> ParseResult<'a, O> {
   move |input| match a(input) {
     Ok(result) => Ok(result),
-    Err(ParseError::Backtrace) => b(input),
+    Err(ParseError::Backtrace(mut labels)) => match b(input) {
+      Ok(result) => Ok(result),
+      Err(ParseError::Backtrace(other_labels)) => {
+        labels.extend(other_labels);
+        Err(ParseError::Backtrace(labels))
+      }
+      err => err,
+    },
     Err(err) => Err(err),
   }
 }
@@ -269,6 +501,18 @@ pub fn or7<'a, O>(
   or6(a, b, c, d, e, or(f, g))
 }
 
+/// Turns a `Backtrace` from the combinator into a `Failure`, anchored
+/// at the current input, so `or`/`or3..or7` stop trying alternatives.
+pub fn cut<'a, O>(
+  combinator: impl Fn(&'a str) -> ParseResult<'a, O>,
+  message: impl AsRef<str>,
+) -> impl Fn(&'a str) -> ParseResult<'a, O> {
+  move |input| match combinator(input) {
+    Err(ParseError::Backtrace(_)) => ParseError::fail(input, message.as_ref()),
+    result => result,
+  }
+}
+
 /// Returns the second value and discards the first.
 pub fn preceded<'a, First, Second>(
   first: impl Fn(&'a str) -> ParseResult<'a, First>,
@@ -369,7 +613,7 @@ pub fn with_error_context<'a, O>(
 ) -> impl Fn(&'a str) -> ParseResult<'a, O> {
   move |input| match combinator(input) {
     Ok(result) => Ok(result),
-    Err(ParseError::Backtrace) => Err(ParseError::Backtrace),
+    Err(err @ ParseError::Backtrace(_)) => Err(err),
     Err(ParseError::Failure(err)) => {
       let mut message = message.to_string();
       message.push_str("\n\n");
@@ -379,21 +623,89 @@ pub fn with_error_context<'a, O>(
   }
 }
 
-/// Keeps consuming a combinator into an array until a condition
+/// A container that a repetition combinator can accumulate parsed
+/// values into (ex. `Vec<O>`, `String`, `()`, `usize`).
+pub trait Accumulate<T> {
+  fn initial(size_hint: Option<usize>) -> Self;
+  fn accumulate(&mut self, item: T);
+}
+
+impl<T> Accumulate<T> for Vec<T> {
+  fn initial(size_hint: Option<usize>) -> Self {
+    match size_hint {
+      Some(size) => Vec::with_capacity(size),
+      None => Vec::new(),
+    }
+  }
+
+  fn accumulate(&mut self, item: T) {
+    self.push(item);
+  }
+}
+
+impl Accumulate<char> for String {
+  fn initial(size_hint: Option<usize>) -> Self {
+    match size_hint {
+      Some(size) => String::with_capacity(size),
+      None => String::new(),
+    }
+  }
+
+  fn accumulate(&mut self, item: char) {
+    self.push(item);
+  }
+}
+
+impl<'a> Accumulate<&'a str> for String {
+  fn initial(size_hint: Option<usize>) -> Self {
+    match size_hint {
+      Some(size) => String::with_capacity(size),
+      None => String::new(),
+    }
+  }
+
+  fn accumulate(&mut self, item: &'a str) {
+    self.push_str(item);
+  }
+}
+
+impl<T> Accumulate<T> for () {
+  fn initial(_size_hint: Option<usize>) -> Self {}
+
+  fn accumulate(&mut self, _item: T) {}
+}
+
+impl<T> Accumulate<T> for usize {
+  fn initial(_size_hint: Option<usize>) -> Self {
+    0
+  }
+
+  fn accumulate(&mut self, _item: T) {
+    *self += 1;
+  }
+}
+
+/// Keeps consuming a combinator into an accumulator until a condition
 /// is met or backtracing occurs.
-pub fn many_till<'a, O, OCondition>(
+pub fn many_till<'a, O, OCondition, C: Accumulate<O>>(
   combinator: impl Fn(&'a str) -> ParseResult<'a, O>,
   condition: impl Fn(&'a str) -> ParseResult<'a, OCondition>,
-) -> impl Fn(&'a str) -> ParseResult<'a, Vec<O>> {
+) -> impl Fn(&'a str) -> ParseResult<'a, C> {
   move |mut input| {
-    let mut results = Vec::new();
+    let mut results = C::initial(None);
     while !input.is_empty() && is_backtrace(condition(input))? {
       match combinator(input) {
         Ok((result_input, value)) => {
-          results.push(value);
+          // guard against an infinite loop when the combinator
+          // matches without consuming any input
+          let progressed = result_input.len() != input.len();
+          results.accumulate(value);
           input = result_input;
+          if !progressed {
+            break;
+          }
         }
-        Err(ParseError::Backtrace) => {
+        Err(ParseError::Backtrace(_)) => {
           return Ok((input, results));
         }
         Err(err) => return Err(err),
@@ -403,28 +715,35 @@ pub fn many_till<'a, O, OCondition>(
   }
 }
 
-/// Keeps consuming a combinator into an array until a condition
-/// is met or backtracing occurs.
-pub fn separated_list<'a, O, OSeparator>(
+/// Keeps consuming a combinator, separated by `separator`, into an
+/// accumulator until backtracing occurs.
+pub fn separated_list<'a, O, OSeparator, C: Accumulate<O>>(
   combinator: impl Fn(&'a str) -> ParseResult<'a, O>,
   separator: impl Fn(&'a str) -> ParseResult<'a, OSeparator>,
-) -> impl Fn(&'a str) -> ParseResult<'a, Vec<O>> {
+) -> impl Fn(&'a str) -> ParseResult<'a, C> {
   move |mut input| {
-    let mut results = Vec::new();
+    let mut results = C::initial(None);
     while !input.is_empty() {
+      // guard against an infinite loop when the combinator
+      // matches without consuming any input
+      let progressed;
       match combinator(input) {
         Ok((result_input, value)) => {
-          results.push(value);
+          progressed = result_input.len() != input.len();
+          results.accumulate(value);
           input = result_input;
         }
-        Err(ParseError::Backtrace) => {
+        Err(ParseError::Backtrace(_)) => {
           return Ok((input, results));
         }
         Err(err) => return Err(err),
       }
+      if !progressed {
+        break;
+      }
       input = match separator(input) {
         Ok((input, _)) => input,
-        Err(ParseError::Backtrace) => break,
+        Err(ParseError::Backtrace(_)) => break,
         Err(err) => return Err(err),
       };
     }
@@ -432,28 +751,96 @@ pub fn separated_list<'a, O, OSeparator>(
   }
 }
 
-/// Applies the combinator 0 or more times and returns a vector
-/// of all the parsed results.
-pub fn many0<'a, O>(
+/// Applies the combinator 0 or more times and accumulates the
+/// parsed results.
+pub fn many0<'a, O, C: Accumulate<O>>(
   combinator: impl Fn(&'a str) -> ParseResult<'a, O>,
-) -> impl Fn(&'a str) -> ParseResult<'a, Vec<O>> {
+) -> impl Fn(&'a str) -> ParseResult<'a, C> {
   many_till(combinator, |_| ParseError::backtrace::<()>())
 }
 
-/// Applies the combinator at least 1 time, but maybe more
-/// and returns a vector of all the parsed results.
-pub fn many1<'a, O>(
+/// Applies the combinator at least 1 time, but maybe more,
+/// and accumulates the parsed results. Note this can't be used with
+/// `C = ()`, since `()` discards enough information to tell whether
+/// zero items were accumulated — use `many0` for that case instead.
+pub fn many1<'a, O, C: Accumulate<O> + IsEmptyable>(
   combinator: impl Fn(&'a str) -> ParseResult<'a, O>,
-) -> impl Fn(&'a str) -> ParseResult<'a, Vec<O>> {
+) -> impl Fn(&'a str) -> ParseResult<'a, C> {
   if_not_empty(many0(combinator))
 }
 
+/// Applies the combinator between `min` and `max` times (inclusive)
+/// and returns a vector of all the parsed results. Backtraces if
+/// fewer than `min` results were collected.
+pub fn many_m_n<'a, O>(
+  min: usize,
+  max: usize,
+  combinator: impl Fn(&'a str) -> ParseResult<'a, O>,
+) -> impl Fn(&'a str) -> ParseResult<'a, Vec<O>> {
+  move |mut input| {
+    let mut results = Vec::new();
+    while results.len() < max {
+      match combinator(input) {
+        Ok((result_input, value)) => {
+          // guard against an infinite loop when the combinator
+          // matches without consuming any input
+          let progressed = result_input.len() != input.len();
+          results.push(value);
+          input = result_input;
+          if !progressed {
+            break;
+          }
+        }
+        Err(ParseError::Backtrace(_)) => break,
+        Err(err) => return Err(err),
+      }
+    }
+    if results.len() < min {
+      ParseError::backtrace()
+    } else {
+      Ok((input, results))
+    }
+  }
+}
+
+/// Repeatedly applies the combinator, folding each success into a
+/// running accumulator via `f` instead of collecting the results into
+/// an `Accumulate` container. Propagates `Failure` and uses the same
+/// zero-progress guard as the other repetition combinators to avoid
+/// infinite loops.
+pub fn fold<'a, O, Acc>(
+  combinator: impl Fn(&'a str) -> ParseResult<'a, O>,
+  init: impl Fn() -> Acc,
+  f: impl Fn(Acc, O) -> Acc,
+) -> impl Fn(&'a str) -> ParseResult<'a, Acc> {
+  move |mut input| {
+    let mut acc = init();
+    loop {
+      match combinator(input) {
+        Ok((result_input, value)) => {
+          // guard against an infinite loop when the combinator
+          // matches without consuming any input
+          let progressed = result_input.len() != input.len();
+          acc = f(acc, value);
+          input = result_input;
+          if !progressed {
+            break;
+          }
+        }
+        Err(ParseError::Backtrace(_)) => break,
+        Err(err) => return Err(err),
+      }
+    }
+    Ok((input, acc))
+  }
+}
+
 /// Skips the whitespace.
 pub fn skip_whitespace(input: &str) -> ParseResult<()> {
   match whitespace(input) {
     Ok((input, _)) => Ok((input, ())),
     // the next char was not a backtrace... continue.
-    Err(ParseError::Backtrace) => Ok((input, ())),
+    Err(ParseError::Backtrace(_)) => Ok((input, ())),
     Err(err) => Err(err),
   }
 }
@@ -513,6 +900,12 @@ impl<T> IsEmptyable for Vec<T> {
   }
 }
 
+impl IsEmptyable for usize {
+  fn is_empty(&self) -> bool {
+    *self == 0
+  }
+}
+
 /// Checks if the combinator result is not empty.
 pub fn if_not_empty<'a, R: IsEmptyable>(
   combinator: impl Fn(&'a str) -> ParseResult<'a, R>,
@@ -548,7 +941,194 @@ pub fn log_result<'a, O: std::fmt::Debug>(
 fn is_backtrace<O>(result: ParseResult<O>) -> Result<bool, ParseError> {
   match result {
     Ok(_) => Ok(false),
-    Err(ParseError::Backtrace) => Ok(true),
+    Err(ParseError::Backtrace(_)) => Ok(true),
     Err(err) => Err(err),
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // a combinator that always succeeds without consuming any input,
+  // used to exercise the zero-progress infinite-loop guards
+  fn succeeds_without_consuming<'a>(input: &'a str) -> ParseResult<'a, ()> {
+    Ok((input, ()))
+  }
+
+  #[test]
+  fn parser_trait_map() {
+    let parser = ch('a').map(|c| c.to_ascii_uppercase());
+    assert_eq!(parser.parse("abc").unwrap(), ("bc", 'A'));
+    assert!(parser.parse("zbc").is_err());
+  }
+
+  #[test]
+  fn parser_trait_or() {
+    let parser = ch('a').or(ch('b'));
+    assert_eq!(parser.parse("abc").unwrap(), ("bc", 'a'));
+    assert_eq!(parser.parse("bcd").unwrap(), ("cd", 'b'));
+    assert!(parser.parse("zcd").is_err());
+  }
+
+  #[test]
+  fn parser_trait_and_then() {
+    let parser = ch('a').and_then(|_| BoxedParser::new(ch('b')));
+    assert_eq!(parser.parse("abc").unwrap(), ("c", 'b'));
+    assert!(parser.parse("acc").is_err());
+    assert!(parser.parse("zcc").is_err());
+  }
+
+  #[test]
+  fn parser_trait_terminated() {
+    let parser = ch('a').terminated(ch(';'));
+    assert_eq!(parser.parse("a;b").unwrap(), ("b", 'a'));
+    assert!(parser.parse("a,b").is_err());
+    assert!(parser.parse("zzz").is_err());
+  }
+
+  #[test]
+  fn parser_trait_preceded() {
+    let parser = ch('a').preceded(ch(';'));
+    assert_eq!(parser.parse(";ab").unwrap(), ("b", 'a'));
+    assert!(parser.parse(",ab").is_err());
+    assert!(parser.parse("zzz").is_err());
+  }
+
+  #[test]
+  fn parser_trait_maybe() {
+    let parser = ch('a').maybe();
+    assert_eq!(parser.parse("abc").unwrap(), ("bc", Some('a')));
+    assert_eq!(parser.parse("zbc").unwrap(), ("zbc", None));
+  }
+
+  #[test]
+  fn cut_passes_through_success() {
+    let parser = cut(ch('a'), "expected 'a'");
+    assert_eq!(parser("abc").unwrap(), ("bc", 'a'));
+  }
+
+  #[test]
+  fn cut_promotes_backtrace_to_failure() {
+    let parser = or(cut(ch('a'), "expected 'a'"), ch('b'));
+    match parser("zzz") {
+      Err(ParseError::Failure(err)) => {
+        assert_eq!(err.message, "expected 'a'");
+        assert_eq!(err.input, "zzz");
+      }
+      other => panic!("expected a failure, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn fold_sums_digits_until_backtrace() {
+    let digit = if_true(next_char, |c| c.is_ascii_digit());
+    let parser = fold(
+      digit,
+      || 0u32,
+      |acc, c| acc * 10 + c.to_digit(10).unwrap(),
+    );
+    let (rest, total) = parser("123abc").unwrap();
+    assert_eq!(total, 123);
+    assert_eq!(rest, "abc");
+  }
+
+  #[test]
+  fn fold_stops_on_zero_progress() {
+    let parser = fold(succeeds_without_consuming, || 0, |acc, ()| acc + 1);
+    let (rest, count) = parser("abc").unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(rest, "abc");
+  }
+
+  #[test]
+  fn expect_and_or_synthesize_expected_one_of_message() {
+    let parser = or3(
+      expect("identifier", ch('a')),
+      expect("number", ch('b')),
+      // duplicate label, should only appear once in the message
+      expect("identifier", ch('a')),
+    );
+    let error = with_failure_handling(parser)("z").unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "expected one of: identifier, number\n  z\n  ~"
+    );
+  }
+
+  #[test]
+  fn expect_does_not_affect_a_successful_match() {
+    let parser = expect("identifier", ch('a'));
+    assert_eq!(parser("abc").unwrap(), ("bc", 'a'));
+  }
+
+  #[test]
+  fn many0_accumulates_into_vec_by_default() {
+    let (rest, items): (&str, Vec<char>) = many0(ch('a'))("aaab").unwrap();
+    assert_eq!(items, vec!['a', 'a', 'a']);
+    assert_eq!(rest, "b");
+  }
+
+  #[test]
+  fn many0_accumulates_chars_into_string() {
+    let (rest, letters): (&str, String) =
+      many0(one_of("abc"))("abcabc123").unwrap();
+    assert_eq!(letters, "abcabc");
+    assert_eq!(rest, "123");
+  }
+
+  #[test]
+  fn many0_accumulates_str_slices_into_string() {
+    let (rest, word): (&str, String) =
+      many0(tag("ab"))("ababab!").unwrap();
+    assert_eq!(word, "ababab");
+    assert_eq!(rest, "!");
+  }
+
+  #[test]
+  fn many0_accumulates_into_unit_without_allocating() {
+    let (rest, ()) = many0(ch(' '))("   abc").unwrap();
+    assert_eq!(rest, "abc");
+  }
+
+  #[test]
+  fn many1_counts_matches_into_usize() {
+    let (rest, count): (&str, usize) = many1(ch('a'))("aaab").unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(rest, "b");
+    assert!(many1::<char, usize>(ch('a'))("bbb").is_err());
+  }
+
+  #[test]
+  fn many_m_n_stops_on_zero_progress() {
+    let (rest, results) =
+      many_m_n(0, 5, succeeds_without_consuming)("abc").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(rest, "abc");
+  }
+
+  #[test]
+  fn separated_list_stops_on_zero_progress_before_separator() {
+    let (rest, results): (&str, Vec<()>) =
+      separated_list(succeeds_without_consuming, ch(','))("abc").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(rest, "abc");
+  }
+
+  #[test]
+  fn parse_with_failure_handling_reports_multibyte_line_and_column() {
+    // "héllo" has a multibyte character on the first line, so the
+    // byte offset of the failure on the second line doesn't line up
+    // with its char/column count unless that's handled correctly
+    let original = "héllo\nw0rld";
+    let parser = preceded(
+      terminated(tag("héllo"), ch('\n')),
+      cut(tag("world"), "expected 'world'"),
+    );
+    let error = parse_with_failure_handling(original, parser).unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("expected 'world'"), "{}", message);
+    assert!(message.contains("(2:1)"), "{}", message);
+    assert!(message.contains("w0rld"), "{}", message);
+  }
+}